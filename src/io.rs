@@ -1,11 +1,34 @@
-use std::{
-    future::Future,
-    io::{Read, Write},
-};
+use core::future::Future;
+
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
 
 use thiserror::Error;
 
-use crate::{BromError, Io, Operation, Step};
+use crate::{BromError, Io, IoSlice, Operation, Step};
+
+/// A concrete bus/transport that can reach a BROM/preloader.
+///
+/// Implementing this rather than [`BromExecute`] directly is enough to drive
+/// any [`Operation`]: UART streams and packet-oriented buses such as USB
+/// bulk endpoints both boil down to reading/writing fixed-size buffers.
+pub trait BromTransport {
+    type Error: From<BromError>;
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Error>;
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error>;
+
+    /// Write data scattered across multiple slices, as if they had first been
+    /// concatenated into one buffer. Transports that support real
+    /// scatter/gather writes should override this; the default falls back to
+    /// writing each slice sequentially.
+    fn write_all_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<(), Self::Error> {
+        for buf in bufs {
+            self.write_all(buf.as_slice())?;
+        }
+        Ok(())
+    }
+}
 
 pub trait BromExecute<E>
 where
@@ -25,6 +48,24 @@ where
     }
 }
 
+impl<T> BromExecute<T::Error> for T
+where
+    T: BromTransport,
+{
+    fn io(&mut self, op: Io<'_>) -> Result<(), T::Error> {
+        match op {
+            Io::ReadData(r) => self.read_exact(r),
+            Io::WriteData(w) => self.write_all(w),
+            Io::WriteDataVectored(w) => self.write_all_vectored(w),
+        }
+    }
+}
+
+// Used by the `std` transport below as well as the `tokio`/`futures`
+// transports further down, which also depend on `std::io` for `IoSlice`,
+// `Vec` and this error type even though their own feature doesn't gate on
+// `std` directly.
+#[cfg(any(feature = "std", feature = "tokio", feature = "futures"))]
 #[derive(Error, Debug)]
 pub enum IOError {
     #[error("I/O error: {0}")]
@@ -33,19 +74,121 @@ pub enum IOError {
     Brom(#[from] BromError),
 }
 
-impl<IO> BromExecute<IOError> for IO
+#[cfg(feature = "std")]
+impl<IO> BromTransport for IO
 where
     IO: Read + Write,
 {
-    fn io(&mut self, op: Io<'_>) -> Result<(), IOError> {
-        match op {
-            Io::ReadData(r) => self.read_exact(r)?,
-            Io::WriteData(w) => self.write_all(w)?,
+    type Error = IOError;
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), IOError> {
+        Read::read_exact(self, buf)?;
+        Ok(())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), IOError> {
+        Write::write_all(self, buf)?;
+        Ok(())
+    }
+
+    fn write_all_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<(), IOError> {
+        let mut iovecs: std::vec::Vec<std::io::IoSlice<'_>> = bufs
+            .iter()
+            .map(|s| s.as_slice())
+            .filter(|s| !s.is_empty())
+            .map(std::io::IoSlice::new)
+            .collect();
+        let mut iovecs = &mut iovecs[..];
+        while !iovecs.is_empty() {
+            let mut written = self.write_vectored(iovecs)?;
+            if written == 0 {
+                return Err(IOError::IO(std::io::Error::new(
+                    std::io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                )));
+            }
+            while written > 0 {
+                if written >= iovecs[0].len() {
+                    written -= iovecs[0].len();
+                    iovecs = &mut iovecs[1..];
+                } else {
+                    Write::write_all(self, &iovecs[0][written..])?;
+                    iovecs = &mut iovecs[1..];
+                    written = 0;
+                }
+            }
         }
         Ok(())
     }
 }
 
+/// Error produced while driving a [`BromTransport`]/[`BromTransportAsync`]
+/// over an `embedded-io`/`embedded-io-async` transport
+#[cfg(any(feature = "embedded-io", feature = "embedded-io-async"))]
+#[derive(Error, Debug)]
+pub enum EmbeddedIoError<E> {
+    #[error("I/O error: {0:?}")]
+    IO(E),
+    #[error("Unexpected end of data")]
+    UnexpectedEof,
+    #[error("Bootrom error: {0}")]
+    Brom(#[from] BromError),
+}
+
+// Mutually exclusive with the `std` impl above: both are blanket impls over
+// any `IO` satisfying their respective read/write traits, so enabling both
+// features at once would be a coherence violation (E0119).
+#[cfg(all(feature = "embedded-io", not(feature = "std")))]
+impl<IO> BromTransport for IO
+where
+    IO: embedded_io::Read + embedded_io::Write,
+{
+    type Error = EmbeddedIoError<IO::Error>;
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+        embedded_io::Read::read_exact(self, buf).map_err(|e| match e {
+            embedded_io::ReadExactError::UnexpectedEof => EmbeddedIoError::UnexpectedEof,
+            embedded_io::ReadExactError::Other(e) => EmbeddedIoError::IO(e),
+        })
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        embedded_io::Write::write_all(self, buf).map_err(EmbeddedIoError::IO)
+    }
+
+    // embedded-io transports are stream-oriented with no scatter/gather
+    // write to fall back to; the default sequential write_all is used.
+}
+
+pub trait BromTransportAsync {
+    type Error: From<BromError>;
+
+    fn read_exact(
+        &mut self,
+        buf: &mut [u8],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+    fn write_all(&mut self, buf: &[u8]) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Write data scattered across multiple slices, as if they had first been
+    /// concatenated into one buffer. Transports that support real
+    /// scatter/gather writes should override this; the default falls back to
+    /// writing each slice sequentially.
+    fn write_all_vectored(
+        &mut self,
+        bufs: &[IoSlice<'_>],
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send
+    where
+        Self: Send,
+    {
+        async move {
+            for buf in bufs {
+                self.write_all(buf.as_slice()).await?;
+            }
+            Ok(())
+        }
+    }
+}
+
 pub trait BromExecuteAsync<E>
 where
     E: From<BromError>,
@@ -68,38 +211,233 @@ where
     }
 }
 
+impl<T> BromExecuteAsync<T::Error> for T
+where
+    T: BromTransportAsync + Send,
+{
+    async fn io(&mut self, op: Io<'_>) -> Result<(), T::Error> {
+        match op {
+            Io::ReadData(r) => self.read_exact(r).await,
+            Io::WriteData(w) => self.write_all(w).await,
+            Io::WriteDataVectored(w) => self.write_all_vectored(w).await,
+        }
+    }
+}
+
 #[cfg(feature = "tokio")]
-impl<IO> BromExecuteAsync<IOError> for IO
+impl<IO> BromTransportAsync for IO
 where
     IO: tokio::io::AsyncWriteExt,
     IO: tokio::io::AsyncReadExt,
     IO: Unpin + Send,
 {
-    async fn io(&mut self, op: Io<'_>) -> Result<(), IOError> {
-        match op {
-            Io::ReadData(r) => {
-                self.read_exact(r).await?;
+    type Error = IOError;
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), IOError> {
+        tokio::io::AsyncReadExt::read_exact(self, buf).await?;
+        Ok(())
+    }
+
+    async fn write_all(&mut self, buf: &[u8]) -> Result<(), IOError> {
+        tokio::io::AsyncWriteExt::write_all(self, buf).await?;
+        Ok(())
+    }
+
+    async fn write_all_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<(), IOError> {
+        let mut iovecs: std::vec::Vec<std::io::IoSlice<'_>> = bufs
+            .iter()
+            .map(|s| s.as_slice())
+            .filter(|s| !s.is_empty())
+            .map(std::io::IoSlice::new)
+            .collect();
+        let mut iovecs = &mut iovecs[..];
+        while !iovecs.is_empty() {
+            let mut written = self.write_vectored(iovecs).await?;
+            if written == 0 {
+                return Err(IOError::IO(std::io::Error::new(
+                    std::io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                )));
+            }
+            while written > 0 {
+                if written >= iovecs[0].len() {
+                    written -= iovecs[0].len();
+                    iovecs = &mut iovecs[1..];
+                } else {
+                    tokio::io::AsyncWriteExt::write_all(self, &iovecs[0][written..]).await?;
+                    iovecs = &mut iovecs[1..];
+                    written = 0;
+                }
             }
-            Io::WriteData(w) => self.write_all(w).await?,
         }
         Ok(())
     }
 }
 
 #[cfg(all(feature = "futures", not(feature = "tokio")))]
-impl<IO> BromExecuteAsync<IOError> for IO
+impl<IO> BromTransportAsync for IO
 where
     IO: futures::AsyncReadExt,
     IO: futures::AsyncWriteExt,
     IO: Unpin + Send,
 {
-    async fn io(&mut self, op: Io<'_>) -> Result<(), IOError> {
-        match op {
-            Io::ReadData(r) => {
-                self.read_exact(r).await?;
+    type Error = IOError;
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), IOError> {
+        futures::AsyncReadExt::read_exact(self, buf).await?;
+        Ok(())
+    }
+
+    async fn write_all(&mut self, buf: &[u8]) -> Result<(), IOError> {
+        futures::AsyncWriteExt::write_all(self, buf).await?;
+        Ok(())
+    }
+
+    async fn write_all_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<(), IOError> {
+        let mut iovecs: std::vec::Vec<std::io::IoSlice<'_>> = bufs
+            .iter()
+            .map(|s| s.as_slice())
+            .filter(|s| !s.is_empty())
+            .map(std::io::IoSlice::new)
+            .collect();
+        let mut iovecs = &mut iovecs[..];
+        while !iovecs.is_empty() {
+            let mut written = self.write_vectored(iovecs).await?;
+            if written == 0 {
+                return Err(IOError::IO(std::io::Error::new(
+                    std::io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                )));
+            }
+            while written > 0 {
+                if written >= iovecs[0].len() {
+                    written -= iovecs[0].len();
+                    iovecs = &mut iovecs[1..];
+                } else {
+                    futures::AsyncWriteExt::write_all(self, &iovecs[0][written..]).await?;
+                    iovecs = &mut iovecs[1..];
+                    written = 0;
+                }
             }
-            Io::WriteData(w) => self.write_all(w).await?,
         }
         Ok(())
     }
 }
+
+// Mutually exclusive with the `tokio`/`futures` impls above, for the same
+// coherence reason as the `embedded-io` impl of `BromTransport`.
+#[cfg(all(
+    feature = "embedded-io-async",
+    not(any(feature = "tokio", feature = "futures"))
+))]
+impl<IO> BromTransportAsync for IO
+where
+    IO: embedded_io_async::Read,
+    IO: embedded_io_async::Write,
+    IO: Send,
+{
+    type Error = EmbeddedIoError<IO::Error>;
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+        embedded_io_async::Read::read_exact(self, buf)
+            .await
+            .map_err(|e| match e {
+                embedded_io_async::ReadExactError::UnexpectedEof => EmbeddedIoError::UnexpectedEof,
+                embedded_io_async::ReadExactError::Other(e) => EmbeddedIoError::IO(e),
+            })
+    }
+
+    async fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        embedded_io_async::Write::write_all(self, buf)
+            .await
+            .map_err(EmbeddedIoError::IO)
+    }
+
+    // embedded-io-async transports are stream-oriented with no scatter/gather
+    // write to fall back to; the default sequential write_all is used.
+}
+
+/// USB bulk transport for MediaTek BROM/preloader devices (VID `0x0e8d`),
+/// mapping [`Io::ReadData`]/[`Io::WriteData`] onto bulk IN/OUT transfers.
+///
+/// `UsbTransport`'s [`BromTransportAsync`] impl below is for the concrete
+/// type, not a blanket impl over `IO: nusb`'s traits, so it can coexist with
+/// the `tokio`/`futures` blanket impls above: `UsbTransport` never
+/// implements `tokio::io::AsyncReadExt`/`futures::AsyncReadExt`, so there's
+/// no overlap for the compiler to reject. `usb` can be enabled alongside
+/// `tokio`/`futures` without a coherence conflict.
+#[cfg(feature = "usb")]
+pub mod usb {
+    use super::BromTransportAsync;
+    use crate::BromError;
+
+    /// MediaTek BROM/preloader USB vendor ID
+    pub const VID: u16 = 0x0e8d;
+
+    #[derive(thiserror::Error, Debug)]
+    pub enum UsbError {
+        #[error("USB transfer error: {0}")]
+        Transfer(#[from] nusb::transfer::TransferError),
+        #[error("Unexpected end of data")]
+        UnexpectedEof,
+        #[error("Bootrom error: {0}")]
+        Brom(#[from] BromError),
+    }
+
+    /// A MediaTek BROM/preloader reached over a USB bulk IN/OUT endpoint pair
+    pub struct UsbTransport {
+        interface: nusb::Interface,
+        bulk_in: u8,
+        bulk_out: u8,
+    }
+
+    impl UsbTransport {
+        /// `bulk_in`/`bulk_out` are the endpoint addresses of the BROM's
+        /// bulk IN/OUT endpoints on `interface`
+        pub fn new(interface: nusb::Interface, bulk_in: u8, bulk_out: u8) -> Self {
+            Self {
+                interface,
+                bulk_in,
+                bulk_out,
+            }
+        }
+    }
+
+    impl BromTransportAsync for UsbTransport {
+        type Error = UsbError;
+
+        async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+            let mut read = 0;
+            while read < buf.len() {
+                let request = nusb::transfer::RequestBuffer::new(buf.len() - read);
+                let data = self
+                    .interface
+                    .bulk_in(self.bulk_in, request)
+                    .await
+                    .into_result()?;
+                if data.is_empty() {
+                    // A zero-length transfer (e.g. a ZLP) can't carry any of
+                    // the remaining bytes; treat it as a short read rather
+                    // than spinning forever.
+                    return Err(UsbError::UnexpectedEof);
+                }
+                buf[read..read + data.len()].copy_from_slice(&data);
+                read += data.len();
+            }
+            Ok(())
+        }
+
+        async fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+            let mut written = 0;
+            while written < buf.len() {
+                let transferred = self
+                    .interface
+                    .bulk_out(self.bulk_out, buf[written..].to_vec())
+                    .await
+                    .into_result()?;
+                written += transferred.actual_length();
+            }
+            Ok(())
+        }
+    }
+}
@@ -1,6 +1,18 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![doc = include_str!("../README.md")]
-use std::fmt::Debug;
+#![no_std]
+// `tokio` and `futures` transports build on `std::io::IoSlice`/`Vec` and the
+// `IOError` in `io.rs`, so they pull in `std` the same as the
+// `std`/`usb` transports do.
+#[cfg(any(
+    feature = "std",
+    feature = "usb",
+    feature = "tokio",
+    feature = "futures"
+))]
+extern crate std;
+
+use core::fmt::Debug;
 
 pub mod io;
 
@@ -12,6 +24,8 @@ enum Command {
     JumpDa64 = 0xde,
     GetHwCode = 0xfd,
     GetHwSwVer = 0xfc,
+    Read32 = 0xd1,
+    Write32 = 0xd2,
 }
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -22,6 +36,10 @@ pub enum BromError {
     IncorrectHandshakeResponse,
     #[error("Unexpected status reported: {0}")]
     UnexpectedStatus(u16),
+    #[error("Checksum mismatch, expected {expected:#06x} got {got:#06x}")]
+    ChecksumMismatch { expected: u16, got: u16 },
+    #[error("Handshake attempts exhausted")]
+    HandshakeTimeout,
 }
 
 /// Operations to be executed by calling code to finish a request
@@ -83,6 +101,21 @@ where
     }
 }
 
+/// A single slice of a larger, scattered buffer passed to
+/// [`Io::WriteDataVectored`]
+#[derive(Debug, Clone, Copy)]
+pub struct IoSlice<'a>(&'a [u8]);
+
+impl<'a> IoSlice<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self(data)
+    }
+
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
 /// IO operation that should be executed
 #[derive(Debug)]
 pub enum Io<'a> {
@@ -90,6 +123,9 @@ pub enum Io<'a> {
     ReadData(&'a mut [u8]),
     /// Write all data over the brom transport
     WriteData(&'a [u8]),
+    /// Write all data over the brom transport, as if the given slices had
+    /// first been concatenated into one buffer
+    WriteDataVectored(&'a [IoSlice<'a>]),
 }
 
 #[derive(Debug)]
@@ -174,6 +210,56 @@ impl Operation for HandShake {
     }
 }
 
+/// Like [`HandShake`], but on a mismatched byte it resets to the start of the
+/// sequence and re-issues the first handshake byte, instead of failing
+/// immediately. This is used to poll a device that is being held in reset
+/// until its bootrom starts answering.
+struct HandShakeRetry {
+    offset: usize,
+    data: [u8; 1],
+    written: bool,
+    attempts: u32,
+    max_attempts: u32,
+}
+
+impl HandShakeRetry {
+    fn new(max_attempts: u32) -> Self {
+        Self {
+            offset: 0,
+            data: [0; 1],
+            written: false,
+            attempts: 0,
+            max_attempts,
+        }
+    }
+}
+
+impl Operation for HandShakeRetry {
+    type Value = ();
+
+    fn step(&mut self) -> Step<'_, Self::Value> {
+        if self.written {
+            self.offset += 1;
+            self.written = false;
+            Step::Io(Io::ReadData(&mut self.data))
+        } else if self.offset > 0 && self.data[0] != !HANDSHAKE[self.offset - 1] {
+            self.offset = 0;
+            self.attempts += 1;
+            if self.attempts >= self.max_attempts {
+                Step::Done(Err(BromError::HandshakeTimeout))
+            } else {
+                self.written = true;
+                Step::Io(Io::WriteData(&HANDSHAKE[0..1]))
+            }
+        } else if self.offset >= HANDSHAKE.len() {
+            Step::Done(Ok(()))
+        } else {
+            self.written = true;
+            Step::Io(Io::WriteData(&HANDSHAKE[self.offset..self.offset + 1]))
+        }
+    }
+}
+
 #[derive(Default)]
 struct CheckStatus {
     status: Read<2>,
@@ -193,6 +279,38 @@ impl Operation for CheckStatus {
     }
 }
 
+struct CheckChecksum {
+    expected: u16,
+    read: Read<2>,
+}
+
+impl CheckChecksum {
+    fn new(expected: u16) -> Self {
+        Self {
+            expected,
+            read: Read::new(),
+        }
+    }
+}
+
+impl Operation for CheckChecksum {
+    type Value = ();
+
+    fn step(&mut self) -> Step<'_, Self::Value> {
+        self.read.step().and_then(|v| {
+            let got = u16::from_be_bytes(v);
+            if got == self.expected {
+                Ok(())
+            } else {
+                Err(BromError::ChecksumMismatch {
+                    expected: self.expected,
+                    got,
+                })
+            }
+        })
+    }
+}
+
 struct WriteData<'a> {
     data: &'a [u8],
     written: bool,
@@ -220,6 +338,33 @@ impl Operation for WriteData<'_> {
     }
 }
 
+struct WriteDataVectored<'a> {
+    data: &'a [IoSlice<'a>],
+    written: bool,
+}
+
+impl<'a> WriteDataVectored<'a> {
+    fn new(data: &'a [IoSlice<'a>]) -> Self {
+        Self {
+            data,
+            written: false,
+        }
+    }
+}
+
+impl Operation for WriteDataVectored<'_> {
+    type Value = ();
+
+    fn step(&mut self) -> Step<'_, Self::Value> {
+        if self.written {
+            Step::Done(Ok(()))
+        } else {
+            self.written = true;
+            Step::Io(Io::WriteDataVectored(self.data))
+        }
+    }
+}
+
 struct Read<const N: usize> {
     in_: [u8; N],
     read: bool,
@@ -253,6 +398,29 @@ impl<const N: usize> Operation for Read<N> {
     }
 }
 
+/// Read N bytes, then check the status word that follows, yielding the
+/// data that was read
+#[derive(Default)]
+struct ReadChecked<const N: usize> {
+    read: Read<N>,
+    status: CheckStatus,
+    value: Option<[u8; N]>,
+}
+
+impl<const N: usize> Operation for ReadChecked<N> {
+    type Value = [u8; N];
+
+    fn step(&mut self) -> Step<'_, Self::Value> {
+        match self.value {
+            Some(value) => self.status.step().map(move |_| value),
+            None => self.read.step().chain(|v| {
+                self.value = Some(v);
+                self.status.step().map(move |_| v)
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum EchoState {
     Out,
@@ -299,6 +467,57 @@ impl<const N: usize> Operation for Echo<N> {
     }
 }
 
+/// Compute the 16-bit checksum the bootrom reports after `SEND_DA`.
+///
+/// The bootrom folds the payload into a running accumulator, XORing in each
+/// big-endian 16-bit word; a trailing odd byte is folded in as the high byte
+/// of a final word with a zero low byte.
+pub fn checksum(data: &[u8]) -> u16 {
+    let mut chunks = data.chunks_exact(2);
+    let sum = (&mut chunks).fold(0u16, |sum, chunk| {
+        sum ^ u16::from_be_bytes([chunk[0], chunk[1]])
+    });
+    match chunks.remainder() {
+        [last] => sum ^ u16::from_be_bytes([*last, 0]),
+        _ => sum,
+    }
+}
+
+/// Like [`checksum`], but for data scattered across multiple slices, as if
+/// they had first been concatenated into one buffer.
+pub fn checksum_vectored(data: &[IoSlice<'_>]) -> u16 {
+    let mut sum = 0u16;
+    // A byte carried over from the end of the previous slice, still waiting
+    // to be paired with the next slice's first byte.
+    let mut pending: Option<u8> = None;
+    for slice in data {
+        let mut bytes = slice.as_slice().iter().copied();
+        if let Some(high) = pending.take() {
+            match bytes.next() {
+                Some(low) => sum ^= u16::from_be_bytes([high, low]),
+                None => {
+                    pending = Some(high);
+                    continue;
+                }
+            }
+        }
+        loop {
+            match (bytes.next(), bytes.next()) {
+                (Some(a), Some(b)) => sum ^= u16::from_be_bytes([a, b]),
+                (Some(a), None) => {
+                    pending = Some(a);
+                    break;
+                }
+                (None, _) => break,
+            }
+        }
+    }
+    if let Some(last) = pending {
+        sum ^= u16::from_be_bytes([last, 0]);
+    }
+    sum
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct HwCode {
     /// Hardware code in hex
@@ -307,6 +526,16 @@ pub struct HwCode {
     pub version: u16,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HwSwVer {
+    /// Hardware subcode
+    pub sub_code: u16,
+    /// Hardware version
+    pub version: u16,
+    /// Software version
+    pub sw_version: u16,
+}
+
 #[derive(Debug)]
 pub struct Brom {
     address: u32,
@@ -319,6 +548,18 @@ impl Brom {
         HandShake::new().map(move |_| Self { address })
     }
 
+    /// Start handshake with the bootrom, restarting from the first handshake
+    /// byte when a read byte doesn't match, and failing with
+    /// [`BromError::HandshakeTimeout`] once `max_attempts` attempts in total
+    /// have failed (so at most `max_attempts - 1` retries). Use
+    /// this while hotplug-racing a device that is held in reset, spamming the
+    /// first handshake byte until the bootrom starts answering, instead of
+    /// failing on the first mismatch. The address indicates where to
+    /// load/execute the Download Agent (DA)
+    pub fn handshake_retry(address: u32, max_attempts: u32) -> impl Operation<Value = Self> {
+        HandShakeRetry::new(max_attempts).map(move |_| Self { address })
+    }
+
     /// Get the hardware information from the bootrom
     pub fn hwcode(&self) -> impl Operation<Value = HwCode> {
         Echo::new([Command::GetHwCode as u8]).chain(Read::new().map(|v: [u8; 4]| {
@@ -331,6 +572,7 @@ impl Brom {
     // Send DA to bootrom memory
     pub fn send_da<'d>(&self, data: &'d [u8]) -> impl Operation<Value = ()> + 'd {
         let len = data.len() as u32;
+        let expected_checksum = checksum(data);
         Echo::new([Command::SendDa as u8])
             .chain(Echo::new(self.address.to_be_bytes()))
             .chain(Echo::new(len.to_be_bytes()))
@@ -338,8 +580,23 @@ impl Brom {
             .chain(Echo::new([0; 4]))
             .chain(CheckStatus::default())
             .chain(WriteData::new(data))
-            // TODO check checksum reported by brom
-            .chain(Read::<2>::new())
+            .chain(CheckChecksum::new(expected_checksum))
+            .chain(CheckStatus::default())
+    }
+
+    // Send DA to bootrom memory, scattered across multiple buffers (e.g. a
+    // header plus an mmap'd body) without first concatenating them
+    pub fn send_da_vectored<'d>(&self, data: &'d [IoSlice<'d>]) -> impl Operation<Value = ()> + 'd {
+        let len: u32 = data.iter().map(|s| s.as_slice().len() as u32).sum();
+        let expected_checksum = checksum_vectored(data);
+        Echo::new([Command::SendDa as u8])
+            .chain(Echo::new(self.address.to_be_bytes()))
+            .chain(Echo::new(len.to_be_bytes()))
+            // Empty signature
+            .chain(Echo::new([0; 4]))
+            .chain(CheckStatus::default())
+            .chain(WriteDataVectored::new(data))
+            .chain(CheckChecksum::new(expected_checksum))
             .chain(CheckStatus::default())
     }
 
@@ -352,10 +609,51 @@ impl Brom {
             .chain(Echo::new([0x64]))
             .chain(CheckStatus::default())
     }
+
+    // Execute a 32 bit DA. Ensure that one has been send first!
+    pub fn jump_da(&self) -> impl Operation<Value = ()> {
+        Echo::new([Command::JumpDa as u8])
+            .chain(Echo::new(self.address.to_be_bytes()))
+            .chain(CheckStatus::default())
+    }
+
+    /// Get the hardware/software version from the bootrom
+    pub fn hw_sw_ver(&self) -> impl Operation<Value = HwSwVer> {
+        Echo::new([Command::GetHwSwVer as u8]).chain(ReadChecked::<6>::default().map(
+            |v: [u8; 6]| HwSwVer {
+                sub_code: u16::from_be_bytes(v[0..2].try_into().unwrap()),
+                version: u16::from_be_bytes(v[2..4].try_into().unwrap()),
+                sw_version: u16::from_be_bytes(v[4..6].try_into().unwrap()),
+            },
+        ))
+    }
+
+    /// Read a 32 bit value from bootrom/preloader memory or a register
+    pub fn read32(&self, address: u32) -> impl Operation<Value = u32> {
+        Echo::new([Command::Read32 as u8])
+            .chain(Echo::new(address.to_be_bytes()))
+            .chain(Echo::new(1u32.to_be_bytes()))
+            .chain(CheckStatus::default())
+            .chain(ReadChecked::<4>::default().map(u32::from_be_bytes))
+    }
+
+    /// Write a 32 bit value to bootrom/preloader memory or a register
+    pub fn write32(&self, address: u32, value: u32) -> impl Operation<Value = ()> {
+        Echo::new([Command::Write32 as u8])
+            .chain(Echo::new(address.to_be_bytes()))
+            .chain(Echo::new(1u32.to_be_bytes()))
+            .chain(CheckStatus::default())
+            .chain(Echo::new(value.to_be_bytes()))
+            .chain(CheckStatus::default())
+    }
 }
 
 #[cfg(test)]
 mod test {
+    extern crate std;
+
+    use std::vec::Vec;
+
     use super::*;
 
     #[derive(Debug)]
@@ -378,6 +676,13 @@ mod test {
                         (ExpectedIo::Write(exp), Io::WriteData(r)) => {
                             assert_eq!(exp, &r, "Mismatched write; step {i}");
                         }
+                        (ExpectedIo::Write(exp), Io::WriteDataVectored(r)) => {
+                            let written: Vec<u8> = r
+                                .iter()
+                                .flat_map(|s| s.as_slice().iter().copied())
+                                .collect();
+                            assert_eq!(exp, &written, "Mismatched vectored write; step {i}");
+                        }
                         (expected, got) => panic!(
                             "Mismatched operation {i}: expected {:?}  got: {:?}",
                             expected, got
@@ -414,6 +719,39 @@ mod test {
         EXPECTED_HANDSHAKE.validate(&mut handshake).unwrap();
     }
 
+    #[test]
+    fn handshake_retry_recovers_after_mismatch() {
+        const HANDSHAKE_RETRY: ExpectedSteps = ExpectedSteps(&[
+            // first attempt: device isn't answering yet
+            ExpectedIo::Write(&[0xa0]),
+            ExpectedIo::Read(&[0x00]),
+            // second attempt: restart from the first byte and succeed
+            ExpectedIo::Write(&[0xa0]),
+            ExpectedIo::Read(&[!0xa0]),
+            ExpectedIo::Write(&[0x0a]),
+            ExpectedIo::Read(&[!0x0a]),
+            ExpectedIo::Write(&[0x50]),
+            ExpectedIo::Read(&[!0x50]),
+            ExpectedIo::Write(&[0x05]),
+            ExpectedIo::Read(&[!0x05]),
+        ]);
+        let mut handshake = Brom::handshake_retry(0x1234, 5);
+        HANDSHAKE_RETRY.validate(&mut handshake).unwrap();
+    }
+
+    #[test]
+    fn handshake_retry_times_out() {
+        const HANDSHAKE_RETRY: ExpectedSteps = ExpectedSteps(&[
+            ExpectedIo::Write(&[0xa0]),
+            ExpectedIo::Read(&[0x00]),
+            ExpectedIo::Write(&[0xa0]),
+            ExpectedIo::Read(&[0x00]),
+        ]);
+        let mut handshake = Brom::handshake_retry(0x1234, 2);
+        let err = HANDSHAKE_RETRY.validate(&mut handshake).unwrap_err();
+        assert!(matches!(err, BromError::HandshakeTimeout));
+    }
+
     #[test]
     fn hwcode() {
         const HWCODE: ExpectedSteps = ExpectedSteps(&[
@@ -453,8 +791,8 @@ mod test {
             ExpectedIo::Read(&[0x00, 0x00]),
             // data
             ExpectedIo::Write(DATA),
-            // checksum; TODO calculate
-            ExpectedIo::Read(&[0x0, 0x0]),
+            // checksum
+            ExpectedIo::Read(&[0x02, 0x06]),
             // status
             ExpectedIo::Read(&[0x0, 0x0]),
         ]);
@@ -463,6 +801,77 @@ mod test {
         SEND_DA.validate(&mut p.send_da(DATA)).unwrap();
     }
 
+    #[test]
+    fn send_da_checksum_mismatch() {
+        const DATA: &[u8] = &[0x1, 0x2, 0x3, 0x4];
+        const SEND_DA: ExpectedSteps = ExpectedSteps(&[
+            ExpectedIo::Write(&[0xd7]),
+            ExpectedIo::Read(&[0xd7]),
+            ExpectedIo::Write(&[0x00, 0x00, 0x12, 0x34]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x12, 0x34]),
+            ExpectedIo::Write(&[0x00, 0x00, 0x00, 0x04]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x00, 0x04]),
+            ExpectedIo::Write(&[0x00, 0x00, 0x00, 0x00]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x00, 0x00]),
+            ExpectedIo::Read(&[0x00, 0x00]),
+            ExpectedIo::Write(DATA),
+            // bootrom reports a different checksum than we computed
+            ExpectedIo::Read(&[0xff, 0xff]),
+        ]);
+        let mut handshake = Brom::handshake(0x1234);
+        let p = EXPECTED_HANDSHAKE.validate(&mut handshake).unwrap();
+        let err = SEND_DA.validate(&mut p.send_da(DATA)).unwrap_err();
+        assert!(matches!(
+            err,
+            BromError::ChecksumMismatch {
+                expected: 0x0206,
+                got: 0xffff
+            }
+        ));
+    }
+
+    #[test]
+    fn checksum_odd_length() {
+        assert_eq!(checksum(&[0x1, 0x2, 0x3]), 0x0102 ^ 0x0300);
+    }
+
+    #[test]
+    fn checksum_vectored_matches_concatenated() {
+        const DATA: &[u8] = &[0x1, 0x2, 0x3, 0x4, 0x5];
+        let scattered = [
+            IoSlice::new(&DATA[0..1]),
+            IoSlice::new(&DATA[1..3]),
+            IoSlice::new(&DATA[3..5]),
+        ];
+        assert_eq!(checksum_vectored(&scattered), checksum(DATA));
+    }
+
+    #[test]
+    fn send_da_vectored() {
+        const HEADER: &[u8] = &[0x1, 0x2];
+        const BODY: &[u8] = &[0x3, 0x4];
+        const SEND_DA: ExpectedSteps = ExpectedSteps(&[
+            ExpectedIo::Write(&[0xd7]),
+            ExpectedIo::Read(&[0xd7]),
+            ExpectedIo::Write(&[0x00, 0x00, 0x12, 0x34]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x12, 0x34]),
+            ExpectedIo::Write(&[0x00, 0x00, 0x00, 0x04]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x00, 0x04]),
+            ExpectedIo::Write(&[0x00, 0x00, 0x00, 0x00]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x00, 0x00]),
+            ExpectedIo::Read(&[0x00, 0x00]),
+            ExpectedIo::Write(&[0x1, 0x2, 0x3, 0x4]),
+            ExpectedIo::Read(&[0x02, 0x06]),
+            ExpectedIo::Read(&[0x0, 0x0]),
+        ]);
+        let scattered = [IoSlice::new(HEADER), IoSlice::new(BODY)];
+        let mut handshake = Brom::handshake(0x1234);
+        let p = EXPECTED_HANDSHAKE.validate(&mut handshake).unwrap();
+        SEND_DA
+            .validate(&mut p.send_da_vectored(&scattered))
+            .unwrap();
+    }
+
     #[test]
     fn jump_da64() {
         const JUMP_DA64: ExpectedSteps = ExpectedSteps(&[
@@ -487,4 +896,96 @@ mod test {
         let p = EXPECTED_HANDSHAKE.validate(&mut handshake).unwrap();
         JUMP_DA64.validate(&mut p.jump_da64()).unwrap();
     }
+
+    #[test]
+    fn jump_da() {
+        const JUMP_DA: ExpectedSteps = ExpectedSteps(&[
+            // cmd
+            ExpectedIo::Write(&[0xd5]),
+            ExpectedIo::Read(&[0xd5]),
+            // address
+            ExpectedIo::Write(&[0x00, 0x00, 0x12, 0x34]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x12, 0x34]),
+            // status
+            ExpectedIo::Read(&[0x00, 0x00]),
+        ]);
+        let mut handshake = Brom::handshake(0x1234);
+        let p = EXPECTED_HANDSHAKE.validate(&mut handshake).unwrap();
+        JUMP_DA.validate(&mut p.jump_da()).unwrap();
+    }
+
+    #[test]
+    fn hw_sw_ver() {
+        const HW_SW_VER: ExpectedSteps = ExpectedSteps(&[
+            ExpectedIo::Write(&[0xfc]),
+            ExpectedIo::Read(&[0xfc]),
+            // value
+            ExpectedIo::Read(&[0x81, 0x88, 0x02, 0x03, 0x00, 0x01]),
+            // status
+            ExpectedIo::Read(&[0x00, 0x00]),
+        ]);
+        let mut handshake = Brom::handshake(0x1234);
+        let p = EXPECTED_HANDSHAKE.validate(&mut handshake).unwrap();
+        let hw_sw_ver = HW_SW_VER.validate(&mut p.hw_sw_ver()).unwrap();
+        assert_eq!(
+            hw_sw_ver,
+            HwSwVer {
+                sub_code: 0x8188,
+                version: 0x0203,
+                sw_version: 0x0001,
+            }
+        );
+    }
+
+    #[test]
+    fn read32() {
+        const READ32: ExpectedSteps = ExpectedSteps(&[
+            // cmd
+            ExpectedIo::Write(&[0xd1]),
+            ExpectedIo::Read(&[0xd1]),
+            // address
+            ExpectedIo::Write(&[0x00, 0x00, 0x12, 0x34]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x12, 0x34]),
+            // count
+            ExpectedIo::Write(&[0x00, 0x00, 0x00, 0x01]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x00, 0x01]),
+            // status
+            ExpectedIo::Read(&[0x00, 0x00]),
+            // value
+            ExpectedIo::Read(&[0xde, 0xad, 0xbe, 0xef]),
+            // status
+            ExpectedIo::Read(&[0x00, 0x00]),
+        ]);
+        let mut handshake = Brom::handshake(0x1234);
+        let p = EXPECTED_HANDSHAKE.validate(&mut handshake).unwrap();
+        let value = READ32.validate(&mut p.read32(0x1234)).unwrap();
+        assert_eq!(value, 0xdeadbeef);
+    }
+
+    #[test]
+    fn write32() {
+        const WRITE32: ExpectedSteps = ExpectedSteps(&[
+            // cmd
+            ExpectedIo::Write(&[0xd2]),
+            ExpectedIo::Read(&[0xd2]),
+            // address
+            ExpectedIo::Write(&[0x00, 0x00, 0x12, 0x34]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x12, 0x34]),
+            // count
+            ExpectedIo::Write(&[0x00, 0x00, 0x00, 0x01]),
+            ExpectedIo::Read(&[0x00, 0x00, 0x00, 0x01]),
+            // status
+            ExpectedIo::Read(&[0x00, 0x00]),
+            // value
+            ExpectedIo::Write(&[0xde, 0xad, 0xbe, 0xef]),
+            ExpectedIo::Read(&[0xde, 0xad, 0xbe, 0xef]),
+            // status
+            ExpectedIo::Read(&[0x00, 0x00]),
+        ]);
+        let mut handshake = Brom::handshake(0x1234);
+        let p = EXPECTED_HANDSHAKE.validate(&mut handshake).unwrap();
+        WRITE32
+            .validate(&mut p.write32(0x1234, 0xdeadbeef))
+            .unwrap();
+    }
 }